@@ -1,7 +1,10 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
 use anyhow::Result;
 use crossterm::event::{self, Event, KeyCode, KeyModifiers};
+use ratatui::widgets::{ListState, TableState};
 
 // Import data structures from mark3.rs
 #[derive(Clone)]
@@ -16,6 +19,10 @@ pub struct Bucket {
     pub name: String,
     pub balance: f64,
     pub bank: Bank,
+    /// Amount tied up by disputed transactions, pending resolution.
+    pub held: f64,
+    /// A charged-back bucket is frozen and rejects further transactions.
+    pub frozen: bool,
 }
 
 #[derive(Clone)]
@@ -31,6 +38,23 @@ pub enum TransactionType {
     Expense,
 }
 
+/// Whether a transaction has settled. Pending transactions count only towards
+/// the projected balance; cleared ones count towards the confirmed balance.
+#[derive(Clone, PartialEq)]
+pub enum TransactionStatus {
+    Pending,
+    Cleared,
+}
+
+/// Where a transaction sits in the dispute lifecycle.
+#[derive(Clone, PartialEq)]
+pub enum TransactionState {
+    Normal,
+    Disputed,
+    Resolved,
+    ChargedBack,
+}
+
 #[derive(Clone)]
 pub struct Transaction {
     pub id: u64,
@@ -39,8 +63,88 @@ pub struct Transaction {
     pub timestamp: u64,
     pub tags: Vec<Tag>,
     pub description: String,
+    pub status: TransactionStatus,
+    pub state: TransactionState,
+}
+
+/// Which of the two stacked transaction lists currently takes selection input.
+#[derive(Clone, Copy, PartialEq)]
+pub enum TransactionFocus {
+    Pending,
+    Cleared,
+}
+
+/// Which level of the bank → bucket → tag hierarchy a sidebar node represents.
+#[derive(Clone, Copy, PartialEq)]
+pub enum SidebarKind {
+    Bank,
+    Bucket,
+    Tag,
+}
+
+/// A single row of the collapsible sidebar tree, flattened for rendering.
+pub struct SidebarNode {
+    pub label: String,
+    pub indent: usize,
+    pub kind: SidebarKind,
+    /// Stable key used to track this node's collapsed state.
+    pub key: String,
+    /// Entity name used to filter the content pane when this node is selected.
+    pub entity: String,
+    pub collapsed: bool,
+    pub has_children: bool,
+}
+
+/// Which pane currently receives selection input.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Focus {
+    Sidebar,
+    Content,
 }
 
+/// A typed failure from a data-mutation operation. The TUI renders its
+/// `Display` into `input.message`, but returning it as a value lets the
+/// mutation logic be exercised without driving the event loop.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RojmerError {
+    /// A numeric field could not be parsed, or the value was otherwise invalid.
+    InvalidAmount(String),
+    /// No bank or bucket matched the name the operation referred to.
+    AccountNotFound(String),
+    /// An expense would drive a balance negative.
+    InsufficientFunds {
+        account: String,
+        balance: f64,
+        requested: f64,
+    },
+    /// An entity with the same identity already exists.
+    DuplicateEntity(String),
+    /// A referenced tag does not exist.
+    TagNotFound(String),
+}
+
+impl std::fmt::Display for RojmerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RojmerError::InvalidAmount(what) => write!(f, "Invalid amount: {}", what),
+            RojmerError::AccountNotFound(name) => write!(f, "Account '{}' not found", name),
+            RojmerError::InsufficientFunds {
+                account,
+                balance,
+                requested,
+            } => write!(
+                f,
+                "Insufficient funds in '{}': balance {:.2}, requested {:.2}",
+                account, balance, requested
+            ),
+            RojmerError::DuplicateEntity(desc) => write!(f, "{} already exists", desc),
+            RojmerError::TagNotFound(name) => write!(f, "Tag '{}' does not exist", name),
+        }
+    }
+}
+
+impl std::error::Error for RojmerError {}
+
 pub enum InputMode {
     Normal,
     Editing {
@@ -66,6 +170,9 @@ pub enum InputField {
     TransactionAmount,
     TransactionTags,
     TransactionDescription,
+    DisputeTxId,
+    ResolveTxId,
+    ChargebackTxId,
 }
 
 pub struct InputState {
@@ -84,6 +191,7 @@ pub struct InputState {
     pub transaction_amount: String,
     pub transaction_tags: String,
     pub transaction_description: String,
+    pub transaction_action_id: String,
     pub message: String,
 }
 
@@ -93,13 +201,67 @@ pub struct App {
     pub menu_state: MenuState,
     pub user_data: UserData,
     pub input: InputState,
+    pub bank_state: TableState,
+    pub bucket_state: TableState,
+    pub tag_state: TableState,
+    pub pending_state: TableState,
+    pub cleared_state: TableState,
+    pub transaction_focus: TransactionFocus,
+    pub column_offset: usize,
+    pub content_tab: ContentTab,
+    pub focus: Focus,
+    pub sidebar_state: ListState,
+    pub collapsed: HashSet<String>,
+    pub selected_entity: Option<(SidebarKind, String)>,
+    /// Directories the data is mirrored across, loaded on start and written on
+    /// every successful add and on quit.
+    pub storage_paths: Vec<PathBuf>,
+    /// The pre-edit snapshot captured by `begin_edit`: the original `UserData`
+    /// plus the `input.message` to restore on `rollback`.
+    edit_snapshot: Option<(UserData, String)>,
+    /// Bounded history of states preceding each committed edit, newest last.
+    /// `undo` pops this to revert the most recent change.
+    undo_stack: Vec<UserData>,
 }
 
+/// How many committed edits can be undone before the oldest is forgotten.
+const UNDO_HISTORY: usize = 20;
+
+// Total number of columns in the transactions table and how many are visible
+// at once (the leftmost "ID" column is always one of the visible columns).
+pub const TRANSACTION_COLUMNS: usize = 5;
+pub const TRANSACTION_VISIBLE_COLUMNS: usize = 3;
+
 pub struct MenuState {
     pub selected: usize,
     pub items: Vec<String>,
 }
 
+/// A view within the content area for the selected category. `List` shows the
+/// raw table; `Summary` shows an aggregate/analytics report over the same data.
+#[derive(Clone, Copy, PartialEq)]
+pub enum ContentTab {
+    List,
+    Summary,
+}
+
+impl ContentTab {
+    /// All variants in header order, so the set can be walked for rendering.
+    pub const ALL: [ContentTab; 2] = [ContentTab::List, ContentTab::Summary];
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            ContentTab::List => "List",
+            ContentTab::Summary => "Summary",
+        }
+    }
+
+    pub fn index(&self) -> usize {
+        ContentTab::ALL.iter().position(|t| t == self).unwrap_or(0)
+    }
+}
+
+#[derive(Clone)]
 pub struct UserData {
     pub bank: Vec<Bank>,
     pub bucket: Vec<Bucket>,
@@ -109,7 +271,15 @@ pub struct UserData {
 
 impl App {
     pub fn new() -> Self {
-        Self {
+        // Storage directories come from ROJMER_STORE (comma-separated) so the
+        // data can be mirrored across directories, defaulting to a local dir.
+        let storage_paths = std::env::var("ROJMER_STORE")
+            .ok()
+            .filter(|s| !s.trim().is_empty())
+            .map(|value| value.split(',').map(|p| PathBuf::from(p.trim())).collect())
+            .unwrap_or_else(|| vec![PathBuf::from("rojmer_data")]);
+
+        let mut app = Self {
             should_quit: false,
             menu_state: MenuState {
                 selected: 0,
@@ -143,9 +313,29 @@ impl App {
                 transaction_amount: String::new(),
                 transaction_tags: String::new(),
                 transaction_description: String::new(),
+                transaction_action_id: String::new(),
                 message: String::new(),
             },
-        }
+            bank_state: TableState::default(),
+            bucket_state: TableState::default(),
+            tag_state: TableState::default(),
+            pending_state: TableState::default(),
+            cleared_state: TableState::default(),
+            transaction_focus: TransactionFocus::Pending,
+            column_offset: 0,
+            content_tab: ContentTab::List,
+            focus: Focus::Sidebar,
+            sidebar_state: ListState::default(),
+            collapsed: HashSet::new(),
+            selected_entity: None,
+            storage_paths,
+            edit_snapshot: None,
+            undo_stack: Vec::new(),
+        };
+
+        // Reload any previously-saved data, surfacing errors in the message bar.
+        app.load();
+        app
     }
 
     pub fn handle_events(&mut self) -> Result<()> {
@@ -163,17 +353,70 @@ impl App {
     fn handle_normal_mode(&mut self, key: KeyCode, modifiers: KeyModifiers) {
         match key {
             KeyCode::Char('q') => {
+                self.save();
                 self.should_quit = true;
             }
-            KeyCode::Up => {
-                if self.menu_state.selected > 0 {
-                    self.menu_state.selected -= 1;
+            KeyCode::Char('u') => self.undo(),
+            KeyCode::Up => match self.focus {
+                Focus::Sidebar => self.select_previous_sidebar(),
+                Focus::Content => self.select_previous_row(),
+            },
+            KeyCode::Down => match self.focus {
+                Focus::Sidebar => self.select_next_sidebar(),
+                Focus::Content => self.select_next_row(),
+            },
+            KeyCode::Char(' ') if self.focus == Focus::Sidebar => {
+                self.activate_sidebar();
+            }
+            KeyCode::Char('p') => {
+                // Toggle focus between the pending and completed transaction lists
+                self.toggle_transaction_focus();
+            }
+            KeyCode::Char('c') => {
+                // Clear (settle) the highlighted pending transaction
+                self.clear_selected_pending();
+            }
+            KeyCode::Char('d') if self.menu_state.selected == 3 => {
+                self.start_dispute_input();
+            }
+            KeyCode::Char('r') if self.menu_state.selected == 3 => {
+                self.start_resolve_input();
+            }
+            KeyCode::Char('b') if self.menu_state.selected == 3 => {
+                self.start_chargeback_input();
+            }
+            KeyCode::Char('m') if self.menu_state.selected == 3 => {
+                let path = self.default_csv_path();
+                self.import_csv(&path);
+            }
+            KeyCode::Char('x') if self.menu_state.selected == 3 => {
+                let path = self.default_csv_path();
+                self.export_csv(&path);
+            }
+            KeyCode::Right => {
+                // Scroll columns when reviewing the transactions table, else
+                // move focus into the content pane.
+                if self.focus == Focus::Content
+                    && self.menu_state.selected == 3
+                    && self.content_tab == ContentTab::List
+                {
+                    self.next_column();
                 } else {
-                    // Wrap around to the bottom
-                    self.menu_state.selected = self.menu_state.items.len() - 1;
+                    self.focus = Focus::Content;
                 }
             }
-            KeyCode::Down => {
+            KeyCode::Left => {
+                if self.focus == Focus::Content
+                    && self.menu_state.selected == 3
+                    && self.content_tab == ContentTab::List
+                {
+                    self.previous_column();
+                } else {
+                    self.focus = Focus::Sidebar;
+                }
+            }
+            KeyCode::PageDown => {
+                // Switch to the next category in the sidebar
                 if self.menu_state.selected < self.menu_state.items.len() - 1 {
                     self.menu_state.selected += 1;
                 } else {
@@ -181,13 +424,32 @@ impl App {
                     self.menu_state.selected = 0;
                 }
             }
-            KeyCode::Enter => {
-                // Handle menu selection
-                if self.menu_state.selected == self.menu_state.items.len() - 1 {
-                    // Exit option selected
-                    self.should_quit = true;
+            KeyCode::PageUp => {
+                // Switch to the previous category in the sidebar
+                if self.menu_state.selected > 0 {
+                    self.menu_state.selected -= 1;
+                } else {
+                    // Wrap around to the bottom
+                    self.menu_state.selected = self.menu_state.items.len() - 1;
                 }
             }
+            KeyCode::Tab => {
+                self.next_content_tab();
+            }
+            KeyCode::BackTab => {
+                self.previous_content_tab();
+            }
+            KeyCode::Enter => match self.focus {
+                // Toggle a tree node when the sidebar is focused...
+                Focus::Sidebar => self.activate_sidebar(),
+                // ...otherwise fall back to the menu's exit selection.
+                Focus::Content => {
+                    if self.menu_state.selected == self.menu_state.items.len() - 1 {
+                        self.save();
+                        self.should_quit = true;
+                    }
+                }
+            },
             KeyCode::Char('n') => {
                 // Start creating a new item based on the current tab
                 match self.menu_state.selected {
@@ -202,6 +464,276 @@ impl App {
         }
     }
 
+    // Return the TableState and row count for the currently selected category.
+    // For transactions the focused pending/cleared list is used.
+    fn active_table_state(&mut self) -> Option<(&mut TableState, usize)> {
+        match self.menu_state.selected {
+            0 => Some((&mut self.bank_state, self.user_data.bank.len())),
+            1 => Some((&mut self.bucket_state, self.user_data.bucket.len())),
+            2 => Some((&mut self.tag_state, self.user_data.tag.len())),
+            3 => {
+                let pending = self.count_transactions(TransactionStatus::Pending);
+                let cleared = self.count_transactions(TransactionStatus::Cleared);
+                match self.transaction_focus {
+                    TransactionFocus::Pending => Some((&mut self.pending_state, pending)),
+                    TransactionFocus::Cleared => Some((&mut self.cleared_state, cleared)),
+                }
+            }
+            _ => None,
+        }
+    }
+
+    // Build the flattened set of currently-visible sidebar nodes: banks at
+    // indent 0, their buckets at indent 1, their tags at indent 2, with the
+    // descendants of a collapsed parent omitted.
+    pub fn sidebar_visible_nodes(&self) -> Vec<SidebarNode> {
+        let mut nodes = Vec::new();
+        for bank in &self.user_data.bank {
+            let bank_key = format!("bank:{}|{}", bank.name, bank.accountnumber);
+            let buckets: Vec<&Bucket> = self.user_data.bucket
+                .iter()
+                .filter(|b| b.bank.name == bank.name && b.bank.accountnumber == bank.accountnumber)
+                .collect();
+            let bank_collapsed = self.collapsed.contains(&bank_key);
+            nodes.push(SidebarNode {
+                label: bank.name.clone(),
+                indent: 0,
+                kind: SidebarKind::Bank,
+                key: bank_key,
+                entity: bank.name.clone(),
+                collapsed: bank_collapsed,
+                has_children: !buckets.is_empty(),
+            });
+            if bank_collapsed {
+                continue;
+            }
+            for bucket in buckets {
+                let bucket_key = format!("bucket:{}", bucket.name);
+                let tags: Vec<&Tag> = self.user_data.tag
+                    .iter()
+                    .filter(|t| t.bucket.name == bucket.name)
+                    .collect();
+                let bucket_collapsed = self.collapsed.contains(&bucket_key);
+                nodes.push(SidebarNode {
+                    label: bucket.name.clone(),
+                    indent: 1,
+                    kind: SidebarKind::Bucket,
+                    key: bucket_key,
+                    entity: bucket.name.clone(),
+                    collapsed: bucket_collapsed,
+                    has_children: !tags.is_empty(),
+                });
+                if bucket_collapsed {
+                    continue;
+                }
+                for tag in tags {
+                    nodes.push(SidebarNode {
+                        label: tag.name.clone(),
+                        indent: 2,
+                        kind: SidebarKind::Tag,
+                        key: format!("tag:{}", tag.name),
+                        entity: tag.name.clone(),
+                        collapsed: false,
+                        has_children: false,
+                    });
+                }
+            }
+        }
+        nodes
+    }
+
+    // Move the sidebar selection down one visible node, wrapping to the top.
+    fn select_next_sidebar(&mut self) {
+        let len = self.sidebar_visible_nodes().len();
+        if len == 0 {
+            self.sidebar_state.select(None);
+            return;
+        }
+        let next = match self.sidebar_state.selected() {
+            Some(i) if i >= len - 1 => 0,
+            Some(i) => i + 1,
+            None => 0,
+        };
+        self.sidebar_state.select(Some(next));
+    }
+
+    // Move the sidebar selection up one visible node, wrapping to the bottom.
+    fn select_previous_sidebar(&mut self) {
+        let len = self.sidebar_visible_nodes().len();
+        if len == 0 {
+            self.sidebar_state.select(None);
+            return;
+        }
+        let previous = match self.sidebar_state.selected() {
+            Some(0) => len - 1,
+            Some(i) => i - 1,
+            None => 0,
+        };
+        self.sidebar_state.select(Some(previous));
+    }
+
+    // Enter/Space on the sidebar: toggle a parent's collapsed state, or filter
+    // the content pane to a selected leaf's transactions.
+    fn activate_sidebar(&mut self) {
+        let nodes = self.sidebar_visible_nodes();
+        let node = match self.sidebar_state.selected().and_then(|sel| nodes.get(sel)) {
+            Some(node) => node,
+            None => return,
+        };
+        if node.has_children {
+            if self.collapsed.contains(&node.key) {
+                self.collapsed.remove(&node.key);
+            } else {
+                self.collapsed.insert(node.key.clone());
+            }
+        } else {
+            self.selected_entity = Some((node.kind, node.entity.clone()));
+            self.input.message = format!("Filtering transactions by '{}'", node.entity);
+        }
+    }
+
+    // Whether a transaction passes the current sidebar leaf filter (if any).
+    pub fn transaction_matches_filter(&self, transaction: &Transaction) -> bool {
+        match &self.selected_entity {
+            None => true,
+            Some((SidebarKind::Tag, name)) => transaction.tags.iter().any(|t| &t.name == name),
+            Some((SidebarKind::Bucket, name)) => transaction.tags.iter().any(|t| &t.bucket.name == name),
+            Some((SidebarKind::Bank, name)) => transaction.tags.iter().any(|t| &t.bucket.bank.name == name),
+        }
+    }
+
+    fn count_transactions(&self, status: TransactionStatus) -> usize {
+        self.user_data.transaction
+            .iter()
+            .filter(|t| t.status == status && self.transaction_matches_filter(t))
+            .count()
+    }
+
+    // Signed effect of transactions with the given status on a bucket (income
+    // adds, expense subtracts). A transaction is attributed only to its owning
+    // (first-tag) bucket, mirroring the money movement in apply_balance_effect
+    // so the derived figures agree with the base balances.
+    pub fn bucket_delta(&self, bucket_name: &str, status: &TransactionStatus) -> f64 {
+        let mut delta = 0.0;
+        for transaction in &self.user_data.transaction {
+            if &transaction.status != status {
+                continue;
+            }
+            // Charged-back transactions have had their base effect reversed.
+            if transaction.state == TransactionState::ChargedBack {
+                continue;
+            }
+            if let Some(owner) = transaction.tags.first() {
+                if owner.bucket.name == bucket_name {
+                    delta += match transaction.transaction_type {
+                        TransactionType::Income => transaction.amount,
+                        TransactionType::Expense => -transaction.amount,
+                    };
+                }
+            }
+        }
+        delta
+    }
+
+    // Toggle which of the two stacked transaction lists receives selection input.
+    fn toggle_transaction_focus(&mut self) {
+        self.transaction_focus = match self.transaction_focus {
+            TransactionFocus::Pending => TransactionFocus::Cleared,
+            TransactionFocus::Cleared => TransactionFocus::Pending,
+        };
+    }
+
+    // Move the highlighted pending transaction to the cleared list.
+    fn clear_selected_pending(&mut self) {
+        if self.menu_state.selected != 3 {
+            return;
+        }
+        let pending_indices: Vec<usize> = self.user_data.transaction
+            .iter()
+            .enumerate()
+            .filter(|(_, t)| t.status == TransactionStatus::Pending && self.transaction_matches_filter(t))
+            .map(|(i, _)| i)
+            .collect();
+
+        match self.pending_state.selected().and_then(|sel| pending_indices.get(sel)) {
+            Some(&idx) => {
+                self.user_data.transaction[idx].status = TransactionStatus::Cleared;
+                self.input.message = "Transaction moved to Completed".to_string();
+                // Keep the pending selection within the now-shorter list.
+                let remaining = pending_indices.len() - 1;
+                if remaining == 0 {
+                    self.pending_state.select(None);
+                } else if let Some(sel) = self.pending_state.selected() {
+                    self.pending_state.select(Some(sel.min(remaining - 1)));
+                }
+            }
+            None => {
+                self.input.message = "No pending transaction selected".to_string();
+            }
+        }
+    }
+
+    // Move the selection down one row in the active table, wrapping to the top
+    fn select_next_row(&mut self) {
+        if let Some((state, len)) = self.active_table_state() {
+            if len == 0 {
+                state.select(None);
+                return;
+            }
+            let next = match state.selected() {
+                Some(i) if i >= len - 1 => 0,
+                Some(i) => i + 1,
+                None => 0,
+            };
+            state.select(Some(next));
+        }
+    }
+
+    // Move the selection up one row in the active table, wrapping to the bottom
+    fn select_previous_row(&mut self) {
+        if let Some((state, len)) = self.active_table_state() {
+            if len == 0 {
+                state.select(None);
+                return;
+            }
+            let previous = match state.selected() {
+                Some(0) => len - 1,
+                Some(i) => i - 1,
+                None => 0,
+            };
+            state.select(Some(previous));
+        }
+    }
+
+    // Move to the next sub-tab in the content area, wrapping around.
+    fn next_content_tab(&mut self) {
+        let next = (self.content_tab.index() + 1) % ContentTab::ALL.len();
+        self.content_tab = ContentTab::ALL[next];
+    }
+
+    // Move to the previous sub-tab in the content area, wrapping around.
+    fn previous_content_tab(&mut self) {
+        let len = ContentTab::ALL.len();
+        let previous = (self.content_tab.index() + len - 1) % len;
+        self.content_tab = ContentTab::ALL[previous];
+    }
+
+    // Scroll the transactions table one column to the right, stopping once the
+    // last column is visible so the offset can never run past the table.
+    fn next_column(&mut self) {
+        let max = TRANSACTION_COLUMNS.saturating_sub(TRANSACTION_VISIBLE_COLUMNS);
+        if self.column_offset < max {
+            self.column_offset += 1;
+        }
+    }
+
+    // Scroll the transactions table one column to the left.
+    fn previous_column(&mut self) {
+        if self.column_offset > 0 {
+            self.column_offset -= 1;
+        }
+    }
+
     fn handle_editing_mode(&mut self, key: KeyCode, modifiers: KeyModifiers, field: InputField) {
         match key {
             KeyCode::Enter | KeyCode::Down => {
@@ -279,6 +811,33 @@ impl App {
         };
     }
 
+    fn start_dispute_input(&mut self) {
+        self.clear_input_fields();
+        self.input.mode = InputMode::Editing {
+            input: String::new(),
+            cursor_position: 0,
+            field: InputField::DisputeTxId,
+        };
+    }
+
+    fn start_resolve_input(&mut self) {
+        self.clear_input_fields();
+        self.input.mode = InputMode::Editing {
+            input: String::new(),
+            cursor_position: 0,
+            field: InputField::ResolveTxId,
+        };
+    }
+
+    fn start_chargeback_input(&mut self) {
+        self.clear_input_fields();
+        self.input.mode = InputMode::Editing {
+            input: String::new(),
+            cursor_position: 0,
+            field: InputField::ChargebackTxId,
+        };
+    }
+
     fn clear_input_fields(&mut self) {
         self.input.bank_name = String::new();
         self.input.bank_account = String::new();
@@ -294,28 +853,82 @@ impl App {
         self.input.transaction_amount = String::new();
         self.input.transaction_tags = String::new();
         self.input.transaction_description = String::new();
+        self.input.transaction_action_id = String::new();
         self.input.message = String::new();
     }
 
     // Implementation of add functions
-    fn add_bank(&mut self) {
-        // Parse bank balance
-        let balance = match self.input.bank_balance.parse::<f64>() {
-            Ok(val) => val,
-            Err(_) => {
-                self.input.message = "Invalid balance format".to_string();
-                return;
+    // Begin a copy-on-write edit session: snapshot the current data and message
+    // so the in-place mutation that follows can be promoted or backed out.
+    fn begin_edit(&mut self) {
+        self.edit_snapshot = Some((self.user_data.clone(), self.input.message.clone()));
+    }
+
+    // Promote the working copy: archive the pre-edit snapshot onto the bounded
+    // undo stack so the change can later be reverted.
+    fn commit(&mut self) {
+        if let Some((original, _)) = self.edit_snapshot.take() {
+            if self.undo_stack.len() >= UNDO_HISTORY {
+                self.undo_stack.remove(0);
             }
-        };
+            self.undo_stack.push(original);
+        }
+    }
+
+    // Discard the working copy, restoring the data and message as they were at
+    // the matching `begin_edit`.
+    fn rollback(&mut self) {
+        if let Some((original, message)) = self.edit_snapshot.take() {
+            self.user_data = original;
+            self.input.message = message;
+        }
+    }
+
+    // Revert the most recently committed change, if any.
+    fn undo(&mut self) {
+        match self.undo_stack.pop() {
+            Some(previous) => {
+                self.user_data = previous;
+                self.input.message = "Reverted last change".to_string();
+                self.save();
+            }
+            None => {
+                self.input.message = "Nothing to undo".to_string();
+            }
+        }
+    }
+
+    // Run a data-mutating add through an edit session: commit on success,
+    // rollback and surface the error otherwise.
+    fn edit<F>(&mut self, op: F)
+    where
+        F: FnOnce(&mut Self) -> Result<(), RojmerError>,
+    {
+        self.begin_edit();
+        match op(self) {
+            Ok(()) => self.commit(),
+            Err(err) => {
+                self.rollback();
+                self.input.message = err.to_string();
+            }
+        }
+    }
+
+    fn add_bank(&mut self) -> Result<(), RojmerError> {
+        // Parse bank balance
+        let balance = self
+            .input
+            .bank_balance
+            .parse::<f64>()
+            .map_err(|_| RojmerError::InvalidAmount(self.input.bank_balance.clone()))?;
 
         // Check if bank already exists
-        if self.user_data.bank.iter().any(|b| 
-            b.name == self.input.bank_name && 
+        if self.user_data.bank.iter().any(|b|
+            b.name == self.input.bank_name &&
             b.accountnumber == self.input.bank_account) {
-                self.input.message = format!(
-                    "Bank with name '{}' and account '{}' already exists",
-                    self.input.bank_name, self.input.bank_account);
-                return;
+                return Err(RojmerError::DuplicateEntity(format!(
+                    "Bank '{}' with account '{}'",
+                    self.input.bank_name, self.input.bank_account)));
         }
 
         // Add new bank
@@ -324,26 +937,26 @@ impl App {
             accountnumber: self.input.bank_account.clone(),
             balance,
         };
-        
+
         self.user_data.bank.push(new_bank);
         self.input.message = "Bank added successfully".to_string();
         self.clear_input_fields();
+        self.save();
+        Ok(())
     }
 
-    fn add_bucket(&mut self) {
+    fn add_bucket(&mut self) -> Result<(), RojmerError> {
         // Parse bucket balance
-        let balance = match self.input.bucket_balance.parse::<f64>() {
-            Ok(val) => val,
-            Err(_) => {
-                self.input.message = "Invalid balance format".to_string();
-                return;
-            }
-        };
+        let balance = self
+            .input
+            .bucket_balance
+            .parse::<f64>()
+            .map_err(|_| RojmerError::InvalidAmount(self.input.bucket_balance.clone()))?;
 
         // Check if bucket already exists
         if self.user_data.bucket.iter().any(|b| b.name == self.input.bucket_name) {
-            self.input.message = format!("Bucket with name '{}' already exists", self.input.bucket_name);
-            return;
+            return Err(RojmerError::DuplicateEntity(format!(
+                "Bucket '{}'", self.input.bucket_name)));
         }
 
         // Find existing bank or create a new one
@@ -366,28 +979,32 @@ impl App {
             name: self.input.bucket_name.clone(),
             balance,
             bank,
+            held: 0.0,
+            frozen: false,
         };
         
         self.user_data.bucket.push(new_bucket);
         self.input.message = "Bucket added successfully".to_string();
         self.clear_input_fields();
+        self.save();
+        Ok(())
     }
 
-    fn add_tag(&mut self) {
+    fn add_tag(&mut self) -> Result<(), RojmerError> {
         // Check if tag already exists
         if self.user_data.tag.iter().any(|t| t.name == self.input.tag_name) {
-            self.input.message = format!("Tag with name '{}' already exists", self.input.tag_name);
-            return;
+            return Err(RojmerError::DuplicateEntity(format!(
+                "Tag '{}'", self.input.tag_name)));
         }
 
-        // Find bucket or show error
-        let bucket = if let Some(existing_bucket) = self.user_data.bucket.iter().find(|b| 
-            b.name == self.input.tag_bucket) {
-                existing_bucket.clone()
-        } else {
-            self.input.message = format!("Bucket with name '{}' does not exist", self.input.tag_bucket);
-            return;
-        };
+        // Find bucket or report it missing
+        let bucket = self
+            .user_data
+            .bucket
+            .iter()
+            .find(|b| b.name == self.input.tag_bucket)
+            .cloned()
+            .ok_or_else(|| RojmerError::AccountNotFound(self.input.tag_bucket.clone()))?;
 
         // Add new tag
         let new_tag = Tag {
@@ -399,27 +1016,28 @@ impl App {
         self.user_data.tag.push(new_tag);
         self.input.message = "Tag added successfully".to_string();
         self.clear_input_fields();
+        self.save();
+        Ok(())
     }
 
-    fn add_transaction(&mut self) {
+    fn add_transaction(&mut self) -> Result<(), RojmerError> {
         // Parse transaction type
         let transaction_type = match self.input.transaction_type.to_lowercase().as_str() {
             "i" | "income" => TransactionType::Income,
             "e" | "expense" => TransactionType::Expense,
-            _ => {
-                self.input.message = "Invalid transaction type. Use 'i' for income or 'e' for expense".to_string();
-                return;
+            other => {
+                return Err(RojmerError::InvalidAmount(format!(
+                    "transaction type '{}' (use 'i' for income or 'e' for expense)",
+                    other)));
             }
         };
 
         // Parse transaction amount
-        let amount = match self.input.transaction_amount.parse::<f64>() {
-            Ok(val) => val,
-            Err(_) => {
-                self.input.message = "Invalid amount format".to_string();
-                return;
-            }
-        };
+        let amount = self
+            .input
+            .transaction_amount
+            .parse::<f64>()
+            .map_err(|_| RojmerError::InvalidAmount(self.input.transaction_amount.clone()))?;
 
         // Parse and validate tags
         let mut tags = Vec::new();
@@ -429,12 +1047,22 @@ impl App {
                 if let Some(tag) = self.user_data.tag.iter().find(|t| t.name == tag_name) {
                     tags.push(tag.clone());
                 } else {
-                    self.input.message = format!("Tag '{}' does not exist", tag_name);
-                    return;
+                    return Err(RojmerError::TagNotFound(tag_name.to_string()));
                 }
             }
         }
 
+        // Reject transactions that would touch a frozen (charged-back) bucket.
+        // This domain rule short-circuits before any money moves; it is not one
+        // of the typed failure modes, so it is surfaced directly.
+        if let Some(name) = self.frozen_bucket(&tags) {
+            self.input.message = format!("Bucket '{}' is frozen; transaction rejected", name);
+            return Ok(());
+        }
+
+        // Move the money against the owning bucket and its backing bank.
+        self.apply_balance_effect(&transaction_type, amount, &tags)?;
+
         // Get timestamp
         use std::time::{SystemTime, UNIX_EPOCH};
         let timestamp = SystemTime::now()
@@ -443,7 +1071,7 @@ impl App {
             .as_secs();
 
         // Create transaction ID
-        let transaction_id = self.user_data.transaction.len() as u64 + 1;
+        let transaction_id = self.next_transaction_id();
 
         // Add new transaction
         let new_transaction = Transaction {
@@ -453,11 +1081,450 @@ impl App {
             timestamp,
             tags,
             description: self.input.transaction_description.clone(),
+            status: TransactionStatus::Pending,
+            state: TransactionState::Normal,
         };
-        
+
         self.user_data.transaction.push(new_transaction);
         self.input.message = "Transaction added successfully".to_string();
         self.clear_input_fields();
+        self.save();
+        Ok(())
+    }
+
+    // The next free transaction id: one past the current maximum, so it never
+    // collides with an existing row regardless of how ids were assigned.
+    fn next_transaction_id(&self) -> u64 {
+        self.user_data.transaction.iter().map(|t| t.id).max().unwrap_or(0) + 1
+    }
+
+    // The bucket a transaction is held against: its first tag's bucket.
+    fn owning_bucket(&self, idx: usize) -> Option<String> {
+        self.user_data.transaction[idx].tags.first().map(|t| t.bucket.name.clone())
+    }
+
+    // The name of the first frozen bucket any of these tags points at, if any.
+    fn frozen_bucket(&self, tags: &[Tag]) -> Option<String> {
+        tags.iter()
+            .map(|t| &t.bucket.name)
+            .find(|name| self.user_data.bucket.iter().any(|b| &b.name == *name && b.frozen))
+            .cloned()
+    }
+
+    // Apply a transaction's effect to the owning bucket (its first tag's bucket)
+    // and that bucket's backing bank, failing if an expense would overdraw.
+    // Tagless transactions have no target, so they move no money. Shared by
+    // add_transaction and import_csv.
+    fn apply_balance_effect(
+        &mut self,
+        transaction_type: &TransactionType,
+        amount: f64,
+        tags: &[Tag],
+    ) -> Result<(), RojmerError> {
+        let bucket_name = match tags.first().map(|t| t.bucket.name.clone()) {
+            Some(name) => name,
+            None => return Ok(()),
+        };
+        let bucket_idx = self
+            .user_data
+            .bucket
+            .iter()
+            .position(|b| b.name == bucket_name)
+            .ok_or_else(|| RojmerError::AccountNotFound(bucket_name.clone()))?;
+        let (bank_name, bank_account) = {
+            let bucket = &self.user_data.bucket[bucket_idx];
+            (bucket.bank.name.clone(), bucket.bank.accountnumber.clone())
+        };
+        let delta = match transaction_type {
+            TransactionType::Expense => {
+                let balance = self.user_data.bucket[bucket_idx].balance;
+                if balance < amount {
+                    return Err(RojmerError::InsufficientFunds {
+                        account: bucket_name,
+                        balance,
+                        requested: amount,
+                    });
+                }
+                -amount
+            }
+            TransactionType::Income => amount,
+        };
+        self.user_data.bucket[bucket_idx].balance += delta;
+        self.user_data.bucket[bucket_idx].bank.balance += delta;
+        if let Some(bank) = self
+            .user_data
+            .bank
+            .iter_mut()
+            .find(|b| b.name == bank_name && b.accountnumber == bank_account)
+        {
+            bank.balance += delta;
+        }
+        Ok(())
+    }
+
+    // Open a dispute on a transaction, moving its amount into the owning
+    // bucket's held balance. A missing id or an already-disputed transaction
+    // is a no-op with an explanatory message.
+    pub fn dispute(&mut self, tx_id: u64) {
+        let idx = match self.user_data.transaction.iter().position(|t| t.id == tx_id) {
+            Some(idx) => idx,
+            None => {
+                self.input.message = format!("No transaction with id {}", tx_id);
+                return;
+            }
+        };
+        if self.user_data.transaction[idx].state != TransactionState::Normal {
+            self.input.message = format!("Transaction {} is not open to dispute", tx_id);
+            return;
+        }
+
+        let amount = self.user_data.transaction[idx].amount;
+        if let Some(name) = self.owning_bucket(idx) {
+            if let Some(bucket) = self.user_data.bucket.iter_mut().find(|b| b.name == name) {
+                bucket.held += amount;
+            }
+        }
+        self.user_data.transaction[idx].state = TransactionState::Disputed;
+        self.input.message = format!("Transaction {} disputed", tx_id);
+    }
+
+    // Resolve a dispute, releasing the held amount back to the bucket.
+    pub fn resolve(&mut self, tx_id: u64) {
+        let idx = match self.user_data.transaction.iter().position(|t| t.id == tx_id) {
+            Some(idx) => idx,
+            None => {
+                self.input.message = format!("No transaction with id {}", tx_id);
+                return;
+            }
+        };
+        if self.user_data.transaction[idx].state != TransactionState::Disputed {
+            self.input.message = format!("Transaction {} is not disputed", tx_id);
+            return;
+        }
+
+        let amount = self.user_data.transaction[idx].amount;
+        if let Some(name) = self.owning_bucket(idx) {
+            if let Some(bucket) = self.user_data.bucket.iter_mut().find(|b| b.name == name) {
+                bucket.held = (bucket.held - amount).max(0.0);
+            }
+        }
+        self.user_data.transaction[idx].state = TransactionState::Resolved;
+        self.input.message = format!("Transaction {} resolved", tx_id);
+    }
+
+    // Charge back a dispute: drop the held amount, reverse the balance effect
+    // the transaction applied, mark it `ChargedBack`, and freeze the owning
+    // bucket so no further transactions referencing it succeed. The row is kept
+    // (marked) rather than removed so the reversal stays consistent with the
+    // derived figures, which skip charged-back transactions.
+    pub fn chargeback(&mut self, tx_id: u64) {
+        let idx = match self.user_data.transaction.iter().position(|t| t.id == tx_id) {
+            Some(idx) => idx,
+            None => {
+                self.input.message = format!("No transaction with id {}", tx_id);
+                return;
+            }
+        };
+        if self.user_data.transaction[idx].state != TransactionState::Disputed {
+            self.input.message = format!("Transaction {} is not disputed", tx_id);
+            return;
+        }
+
+        let amount = self.user_data.transaction[idx].amount;
+        // Undo the debit/credit add_transaction applied to the base balances.
+        let reversal = match self.user_data.transaction[idx].transaction_type {
+            TransactionType::Income => -amount,
+            TransactionType::Expense => amount,
+        };
+        if let Some(name) = self.owning_bucket(idx) {
+            let bank_key = self
+                .user_data
+                .bucket
+                .iter()
+                .find(|b| b.name == name)
+                .map(|b| (b.bank.name.clone(), b.bank.accountnumber.clone()));
+            if let Some(bucket) = self.user_data.bucket.iter_mut().find(|b| b.name == name) {
+                bucket.held = (bucket.held - amount).max(0.0);
+                bucket.balance += reversal;
+                bucket.bank.balance += reversal;
+                bucket.frozen = true;
+            }
+            if let Some((bank_name, bank_account)) = bank_key {
+                if let Some(bank) = self
+                    .user_data
+                    .bank
+                    .iter_mut()
+                    .find(|b| b.name == bank_name && b.accountnumber == bank_account)
+                {
+                    bank.balance += reversal;
+                }
+            }
+        }
+        self.user_data.transaction[idx].state = TransactionState::ChargedBack;
+        self.input.message = format!("Transaction {} charged back", tx_id);
+    }
+
+    // Load and merge the stored entities from every storage directory. Each
+    // entity kind lives in its own tab-separated file; rows from all
+    // directories are merged with duplicates (by identity) dropped. Missing
+    // files are fine; genuine read errors are reported via input.message.
+    pub fn load(&mut self) {
+        let mut banks: Vec<Bank> = Vec::new();
+        let mut buckets: Vec<Bucket> = Vec::new();
+        let mut tags: Vec<Tag> = Vec::new();
+        let mut transactions: Vec<Transaction> = Vec::new();
+        let mut errors: Vec<String> = Vec::new();
+
+        for dir in &self.storage_paths {
+            for line in Self::read_entity_file(dir, "banks", &mut errors) {
+                if let Some(bank) = parse_bank(&line) {
+                    if !banks.iter().any(|b| b.name == bank.name && b.accountnumber == bank.accountnumber) {
+                        banks.push(bank);
+                    }
+                }
+            }
+            for line in Self::read_entity_file(dir, "buckets", &mut errors) {
+                if let Some(bucket) = parse_bucket(&line, &banks) {
+                    if !buckets.iter().any(|b| b.name == bucket.name) {
+                        buckets.push(bucket);
+                    }
+                }
+            }
+            for line in Self::read_entity_file(dir, "tags", &mut errors) {
+                if let Some(tag) = parse_tag(&line, &buckets) {
+                    if !tags.iter().any(|t| t.name == tag.name) {
+                        tags.push(tag);
+                    }
+                }
+            }
+            for line in Self::read_entity_file(dir, "transactions", &mut errors) {
+                if let Some(transaction) = parse_transaction(&line, &tags) {
+                    if !transactions.iter().any(|t| t.id == transaction.id) {
+                        transactions.push(transaction);
+                    }
+                }
+            }
+        }
+
+        self.user_data = UserData {
+            bank: banks,
+            bucket: buckets,
+            tag: tags,
+            transaction: transactions,
+        };
+
+        if !errors.is_empty() {
+            self.input.message = format!("Load warnings: {}", errors.join("; "));
+        }
+    }
+
+    // Read a single entity file's non-empty lines, treating a missing file as
+    // empty and recording other I/O errors.
+    fn read_entity_file(dir: &Path, kind: &str, errors: &mut Vec<String>) -> Vec<String> {
+        let path = dir.join(format!("{}.tsv", kind));
+        match std::fs::read_to_string(&path) {
+            Ok(contents) => contents.lines().filter(|l| !l.trim().is_empty()).map(String::from).collect(),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(err) => {
+                errors.push(format!("{}: {}", path.display(), err));
+                Vec::new()
+            }
+        }
+    }
+
+    // Mirror the whole UserData to every storage directory, one file per entity
+    // kind. Failures are surfaced through input.message instead of panicking.
+    pub fn save(&mut self) {
+        let banks: String = self.user_data.bank.iter().map(format_bank).collect();
+        let buckets: String = self.user_data.bucket.iter().map(format_bucket).collect();
+        let tags: String = self.user_data.tag.iter().map(format_tag).collect();
+        let transactions: String = self.user_data.transaction.iter().map(format_transaction).collect();
+
+        let mut errors: Vec<String> = Vec::new();
+        for dir in &self.storage_paths {
+            if let Err(err) = std::fs::create_dir_all(dir) {
+                errors.push(format!("{}: {}", dir.display(), err));
+                continue;
+            }
+            for (kind, data) in [
+                ("banks", &banks),
+                ("buckets", &buckets),
+                ("tags", &tags),
+                ("transactions", &transactions),
+            ] {
+                let path = dir.join(format!("{}.tsv", kind));
+                if let Err(err) = std::fs::write(&path, data.as_bytes()) {
+                    errors.push(format!("{}: {}", path.display(), err));
+                }
+            }
+        }
+
+        if !errors.is_empty() {
+            self.input.message = format!("Save failed: {}", errors.join("; "));
+        }
+    }
+
+    // The conventional ledger path used by the import/export keybindings:
+    // `ledger.csv` in the first storage directory, or the working directory
+    // when no storage directory is configured.
+    fn default_csv_path(&self) -> String {
+        match self.storage_paths.first() {
+            Some(dir) => dir.join("ledger.csv").to_string_lossy().into_owned(),
+            None => "ledger.csv".to_string(),
+        }
+    }
+
+    // Import a flat CSV ledger (`type,client,tx,amount,tags,description`),
+    // appending each parsed row to the transaction list. Tag validation reuses
+    // the same by-name lookup as `add_transaction`. Unparseable or unknown-tag
+    // rows are collected into a per-row report instead of aborting the import.
+    pub fn import_csv(&mut self, path: &str) {
+        let contents = match std::fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(err) => {
+                self.input.message = format!("Failed to read '{}': {}", path, err);
+                return;
+            }
+        };
+
+        let mut imported = 0;
+        let mut errors: Vec<String> = Vec::new();
+
+        for (line_no, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            // Skip an optional header row.
+            if line_no == 0 && line.to_lowercase().starts_with("type,") {
+                continue;
+            }
+
+            let fields: Vec<&str> = line.split(',').collect();
+            if fields.len() < 4 {
+                errors.push(format!("row {}: expected at least 4 columns", line_no + 1));
+                continue;
+            }
+
+            let transaction_type = match fields[0].trim().to_lowercase().as_str() {
+                "i" | "income" => TransactionType::Income,
+                "e" | "expense" => TransactionType::Expense,
+                other => {
+                    errors.push(format!("row {}: invalid type '{}'", line_no + 1, other));
+                    continue;
+                }
+            };
+
+            let amount = match fields[3].trim().parse::<f64>() {
+                Ok(value) => value,
+                Err(_) => {
+                    errors.push(format!("row {}: invalid amount '{}'", line_no + 1, fields[3].trim()));
+                    continue;
+                }
+            };
+
+            // Tags live in column 4, separated by ';' so commas stay as the
+            // field delimiter. Look each one up exactly as add_transaction does.
+            let mut tags = Vec::new();
+            let mut unknown_tag = None;
+            for name in fields.get(4).copied().unwrap_or("").split(';') {
+                let name = name.trim();
+                if name.is_empty() {
+                    continue;
+                }
+                match self.user_data.tag.iter().find(|t| t.name == name) {
+                    Some(tag) => tags.push(tag.clone()),
+                    None => {
+                        unknown_tag = Some(name.to_string());
+                        break;
+                    }
+                }
+            }
+            if let Some(name) = unknown_tag {
+                errors.push(format!("row {}: unknown tag '{}'", line_no + 1, name));
+                continue;
+            }
+
+            // Enforce the same rules add_transaction applies: reject frozen
+            // buckets and move the money, skipping (not aborting) on failure.
+            if let Some(name) = self.frozen_bucket(&tags) {
+                errors.push(format!("row {}: bucket '{}' is frozen", line_no + 1, name));
+                continue;
+            }
+            if let Err(err) = self.apply_balance_effect(&transaction_type, amount, &tags) {
+                errors.push(format!("row {}: {}", line_no + 1, err));
+                continue;
+            }
+
+            let description = fields.get(5).copied().unwrap_or("").trim().to_string();
+            // Honor the `tx` column's id only when it is present and does not
+            // collide with an already-loaded transaction (including rows
+            // imported earlier in this batch); otherwise derive a unique id.
+            let id = match fields[2].trim().parse::<u64>() {
+                Ok(parsed) if !self.user_data.transaction.iter().any(|t| t.id == parsed) => parsed,
+                _ => self.next_transaction_id(),
+            };
+
+            use std::time::{SystemTime, UNIX_EPOCH};
+            let timestamp = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0);
+
+            self.user_data.transaction.push(Transaction {
+                id,
+                transaction_type,
+                amount,
+                timestamp,
+                tags,
+                description,
+                status: TransactionStatus::Pending,
+                state: TransactionState::Normal,
+            });
+            imported += 1;
+        }
+
+        self.input.message = if errors.is_empty() {
+            format!("Imported {} transaction(s)", imported)
+        } else {
+            format!(
+                "Imported {} transaction(s), {} row(s) skipped: {}",
+                imported,
+                errors.len(),
+                errors.join("; ")
+            )
+        };
+        self.save();
+    }
+
+    // Serialize the current ledger back out to a flat CSV file, matching the
+    // layout that `import_csv` reads.
+    pub fn export_csv(&mut self, path: &str) {
+        let mut out = String::from("type,client,tx,amount,tags,description\n");
+        for transaction in &self.user_data.transaction {
+            let type_str = match transaction.transaction_type {
+                TransactionType::Income => "income",
+                TransactionType::Expense => "expense",
+            };
+            let tags = transaction.tags
+                .iter()
+                .map(|tag| tag.name.clone())
+                .collect::<Vec<String>>()
+                .join(";");
+            out.push_str(&format!(
+                "{},{},{},{:.2},{},{}\n",
+                type_str, "", transaction.id, transaction.amount, tags, transaction.description
+            ));
+        }
+
+        self.input.message = match std::fs::write(path, out) {
+            Ok(()) => format!(
+                "Exported {} transaction(s) to '{}'",
+                self.user_data.transaction.len(),
+                path
+            ),
+            Err(err) => format!("Failed to write '{}': {}", path, err),
+        };
     }
 
     // New helper methods for editing mode navigation
@@ -471,7 +1538,7 @@ impl App {
                 self.switch_to_field(InputField::BankBalance, self.input.bank_balance.clone());
             }
             InputField::BankBalance => {
-                self.add_bank();
+                self.edit(Self::add_bank);
                 self.input.mode = InputMode::Normal;
             }
             
@@ -486,7 +1553,7 @@ impl App {
                 self.switch_to_field(InputField::BucketAccountNumber, self.input.bucket_account.clone());
             }
             InputField::BucketAccountNumber => {
-                self.add_bucket();
+                self.edit(Self::add_bucket);
                 self.input.mode = InputMode::Normal;
             }
             
@@ -498,7 +1565,7 @@ impl App {
                 self.switch_to_field(InputField::TagDescription, self.input.tag_description.clone());
             }
             InputField::TagDescription => {
-                self.add_tag();
+                self.edit(Self::add_tag);
                 self.input.mode = InputMode::Normal;
             }
             
@@ -513,7 +1580,30 @@ impl App {
                 self.switch_to_field(InputField::TransactionDescription, self.input.transaction_description.clone());
             }
             InputField::TransactionDescription => {
-                self.add_transaction();
+                self.edit(Self::add_transaction);
+                self.input.mode = InputMode::Normal;
+            }
+
+            // Dispute lifecycle actions: a single id field commits immediately.
+            InputField::DisputeTxId => {
+                match self.input.transaction_action_id.trim().parse::<u64>() {
+                    Ok(id) => self.dispute(id),
+                    Err(_) => self.input.message = "Invalid transaction id".to_string(),
+                }
+                self.input.mode = InputMode::Normal;
+            }
+            InputField::ResolveTxId => {
+                match self.input.transaction_action_id.trim().parse::<u64>() {
+                    Ok(id) => self.resolve(id),
+                    Err(_) => self.input.message = "Invalid transaction id".to_string(),
+                }
+                self.input.mode = InputMode::Normal;
+            }
+            InputField::ChargebackTxId => {
+                match self.input.transaction_action_id.trim().parse::<u64>() {
+                    Ok(id) => self.chargeback(id),
+                    Err(_) => self.input.message = "Invalid transaction id".to_string(),
+                }
                 self.input.mode = InputMode::Normal;
             }
         }
@@ -570,6 +1660,11 @@ impl App {
             InputField::TransactionDescription => {
                 self.switch_to_field(InputField::TransactionTags, self.input.transaction_tags.clone());
             }
+
+            // The single-field dispute actions have no previous field.
+            InputField::DisputeTxId
+            | InputField::ResolveTxId
+            | InputField::ChargebackTxId => {}
         }
     }
 
@@ -598,6 +1693,285 @@ impl App {
             InputField::TransactionAmount => self.input.transaction_amount = value.to_string(),
             InputField::TransactionTags => self.input.transaction_tags = value.to_string(),
             InputField::TransactionDescription => self.input.transaction_description = value.to_string(),
+            InputField::DisputeTxId
+            | InputField::ResolveTxId
+            | InputField::ChargebackTxId => self.input.transaction_action_id = value.to_string(),
         }
     }
-}
\ No newline at end of file
+}
+
+// --- On-disk (de)serialization, one tab-separated line per entity ---
+
+fn format_bank(bank: &Bank) -> String {
+    format!("{}\t{}\t{}\n", bank.name, bank.accountnumber, bank.balance)
+}
+
+fn parse_bank(line: &str) -> Option<Bank> {
+    let fields: Vec<&str> = line.split('\t').collect();
+    if fields.len() < 3 {
+        return None;
+    }
+    let balance = fields[2].trim().parse::<f64>().ok()?;
+    Some(Bank {
+        name: fields[0].to_string(),
+        accountnumber: fields[1].to_string(),
+        balance,
+    })
+}
+
+fn format_bucket(bucket: &Bucket) -> String {
+    format!(
+        "{}\t{}\t{}\t{}\t{}\t{}\n",
+        bucket.name, bucket.balance, bucket.bank.name, bucket.bank.accountnumber, bucket.held, bucket.frozen
+    )
+}
+
+fn parse_bucket(line: &str, banks: &[Bank]) -> Option<Bucket> {
+    let fields: Vec<&str> = line.split('\t').collect();
+    if fields.len() < 6 {
+        return None;
+    }
+    let balance = fields[1].trim().parse::<f64>().ok()?;
+    let held = fields[4].trim().parse::<f64>().ok()?;
+    let frozen = fields[5].trim() == "true";
+    let bank = banks
+        .iter()
+        .find(|b| b.name == fields[2] && b.accountnumber == fields[3])
+        .cloned()
+        .unwrap_or(Bank {
+            name: fields[2].to_string(),
+            accountnumber: fields[3].to_string(),
+            balance: 0.0,
+        });
+    Some(Bucket {
+        name: fields[0].to_string(),
+        balance,
+        bank,
+        held,
+        frozen,
+    })
+}
+
+fn format_tag(tag: &Tag) -> String {
+    format!("{}\t{}\t{}\n", tag.name, tag.description, tag.bucket.name)
+}
+
+fn parse_tag(line: &str, buckets: &[Bucket]) -> Option<Tag> {
+    let fields: Vec<&str> = line.split('\t').collect();
+    if fields.len() < 3 {
+        return None;
+    }
+    let bucket = buckets
+        .iter()
+        .find(|b| b.name == fields[2])
+        .cloned()
+        .unwrap_or(Bucket {
+            name: fields[2].to_string(),
+            balance: 0.0,
+            bank: Bank {
+                name: String::new(),
+                accountnumber: String::new(),
+                balance: 0.0,
+            },
+            held: 0.0,
+            frozen: false,
+        });
+    Some(Tag {
+        name: fields[0].to_string(),
+        description: fields[1].to_string(),
+        bucket,
+    })
+}
+
+fn format_transaction(transaction: &Transaction) -> String {
+    let type_str = match transaction.transaction_type {
+        TransactionType::Income => "income",
+        TransactionType::Expense => "expense",
+    };
+    let tags = transaction.tags
+        .iter()
+        .map(|t| t.name.clone())
+        .collect::<Vec<String>>()
+        .join(";");
+    let status = match transaction.status {
+        TransactionStatus::Pending => "pending",
+        TransactionStatus::Cleared => "cleared",
+    };
+    let state = match transaction.state {
+        TransactionState::Normal => "normal",
+        TransactionState::Disputed => "disputed",
+        TransactionState::Resolved => "resolved",
+        TransactionState::ChargedBack => "chargedback",
+    };
+    format!(
+        "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\n",
+        transaction.id, type_str, transaction.amount, transaction.timestamp, tags, transaction.description, status, state
+    )
+}
+
+fn parse_transaction(line: &str, all_tags: &[Tag]) -> Option<Transaction> {
+    let fields: Vec<&str> = line.split('\t').collect();
+    if fields.len() < 8 {
+        return None;
+    }
+    let id = fields[0].trim().parse::<u64>().ok()?;
+    let transaction_type = match fields[1].trim() {
+        "income" => TransactionType::Income,
+        "expense" => TransactionType::Expense,
+        _ => return None,
+    };
+    let amount = fields[2].trim().parse::<f64>().ok()?;
+    let timestamp = fields[3].trim().parse::<u64>().ok()?;
+    let tags = fields[4]
+        .split(';')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .filter_map(|name| all_tags.iter().find(|t| t.name == name).cloned())
+        .collect();
+    let status = match fields[6].trim() {
+        "cleared" => TransactionStatus::Cleared,
+        _ => TransactionStatus::Pending,
+    };
+    let state = match fields[7].trim() {
+        "disputed" => TransactionState::Disputed,
+        "resolved" => TransactionState::Resolved,
+        "chargedback" => TransactionState::ChargedBack,
+        _ => TransactionState::Normal,
+    };
+    Some(Transaction {
+        id,
+        transaction_type,
+        amount,
+        timestamp,
+        tags,
+        description: fields[5].to_string(),
+        status,
+        state,
+    })
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A three-entity fixture (one bank, one bucket, one tag) with no storage
+    // directories, so the saves made by add_* and import_csv write nothing.
+    fn sample_app() -> App {
+        let mut app = App::new();
+        app.storage_paths.clear();
+        let bank = Bank {
+            name: "Checking".to_string(),
+            accountnumber: "001".to_string(),
+            balance: 500.0,
+        };
+        let bucket = Bucket {
+            name: "Groceries".to_string(),
+            balance: 500.0,
+            bank: bank.clone(),
+            held: 0.0,
+            frozen: false,
+        };
+        let tag = Tag {
+            name: "food".to_string(),
+            description: String::new(),
+            bucket: bucket.clone(),
+        };
+        app.user_data = UserData {
+            bank: vec![bank],
+            bucket: vec![bucket],
+            tag: vec![tag],
+            transaction: Vec::new(),
+        };
+        app
+    }
+
+    fn approx(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-6
+    }
+
+    // Fill in the transaction form fields the way the editing flow would.
+    fn enter_transaction(app: &mut App, kind: &str, amount: &str, tags: &str) {
+        app.input.transaction_type = kind.to_string();
+        app.input.transaction_amount = amount.to_string();
+        app.input.transaction_tags = tags.to_string();
+        app.input.transaction_description = String::new();
+    }
+
+    #[test]
+    fn expense_exceeding_balance_is_rejected() {
+        let mut app = sample_app();
+        enter_transaction(&mut app, "e", "600", "food");
+        let result = app.add_transaction();
+        assert!(matches!(result, Err(RojmerError::InsufficientFunds { .. })));
+        assert!(approx(app.user_data.bucket[0].balance, 500.0));
+        assert!(app.user_data.transaction.is_empty());
+    }
+
+    #[test]
+    fn expense_within_balance_debits_bucket_and_bank() {
+        let mut app = sample_app();
+        enter_transaction(&mut app, "e", "100", "food");
+        assert!(app.add_transaction().is_ok());
+        assert!(approx(app.user_data.bucket[0].balance, 400.0));
+        assert!(approx(app.user_data.bank[0].balance, 400.0));
+    }
+
+    #[test]
+    fn dispute_then_resolve_roundtrips_held() {
+        let mut app = sample_app();
+        enter_transaction(&mut app, "e", "100", "food");
+        app.add_transaction().unwrap();
+        let id = app.user_data.transaction[0].id;
+
+        app.dispute(id);
+        assert!(app.user_data.transaction[0].state == TransactionState::Disputed);
+        assert!(approx(app.user_data.bucket[0].held, 100.0));
+
+        app.resolve(id);
+        assert!(app.user_data.transaction[0].state == TransactionState::Resolved);
+        assert!(approx(app.user_data.bucket[0].held, 0.0));
+    }
+
+    #[test]
+    fn chargeback_reverses_balance_and_freezes_bucket() {
+        let mut app = sample_app();
+        enter_transaction(&mut app, "e", "100", "food");
+        app.add_transaction().unwrap();
+        let id = app.user_data.transaction[0].id;
+
+        app.dispute(id);
+        app.chargeback(id);
+
+        assert!(app.user_data.transaction[0].state == TransactionState::ChargedBack);
+        // The debit is reversed and the delta no longer counts the row.
+        assert!(approx(app.user_data.bucket[0].balance, 500.0));
+        assert!(approx(app.user_data.bank[0].balance, 500.0));
+        assert!(app.user_data.bucket[0].frozen);
+        assert!(approx(
+            app.bucket_delta("Groceries", &TransactionStatus::Pending),
+            0.0
+        ));
+    }
+
+    #[test]
+    fn csv_export_import_roundtrips() {
+        let mut app = sample_app();
+        enter_transaction(&mut app, "i", "50", "food");
+        app.add_transaction().unwrap();
+
+        let dir = std::env::temp_dir().join(format!("rojmer_csv_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("ledger.csv");
+        let path_str = path.to_string_lossy().into_owned();
+        app.export_csv(&path_str);
+
+        let mut restored = sample_app();
+        restored.import_csv(&path_str);
+
+        assert_eq!(restored.user_data.transaction.len(), 1);
+        assert!(approx(restored.user_data.transaction[0].amount, 50.0));
+        // The imported income is credited to the bucket balance.
+        assert!(approx(restored.user_data.bucket[0].balance, 550.0));
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
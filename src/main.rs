@@ -15,7 +15,7 @@ fn main() -> Result<()> {
     // Main loop
     while !app.should_quit {
         // Draw UI
-        terminal.draw(|f| ui::render(f, &app))?;
+        terminal.draw(|f| ui::render(f, &mut app))?;
         
         // Handle events
         app.handle_events()?;
@@ -0,0 +1,44 @@
+use std::io::{stdout, Stdout};
+use std::panic;
+
+use anyhow::Result;
+use crossterm::{
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use ratatui::backend::CrosstermBackend;
+use ratatui::Terminal;
+
+/// The terminal type used throughout the app: a crossterm backend on stdout.
+pub type Tui = Terminal<CrosstermBackend<Stdout>>;
+
+/// Enter raw mode and the alternate screen, install the panic-safety hook, and
+/// hand back a ready-to-use terminal.
+pub fn init() -> Result<Tui> {
+    enable_raw_mode()?;
+    execute!(stdout(), EnterAlternateScreen)?;
+    install_panic_hook();
+    Terminal::new(CrosstermBackend::new(stdout())).map_err(Into::into)
+}
+
+/// Leave the alternate screen and disable raw mode, returning the terminal to
+/// its normal state.
+pub fn restore(mut terminal: Tui) -> Result<()> {
+    disable_raw_mode()?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    terminal.show_cursor()?;
+    Ok(())
+}
+
+/// Chain a hook onto the existing panic handler that performs the same teardown
+/// as [`restore`] before the original handler prints the message, so a panic
+/// inside the draw closure or event loop leaves a clean, readable terminal.
+fn install_panic_hook() {
+    let original = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        // Best-effort teardown; we are already panicking, so ignore errors.
+        let _ = disable_raw_mode();
+        let _ = execute!(stdout(), LeaveAlternateScreen, crossterm::cursor::Show);
+        original(info);
+    }));
+}
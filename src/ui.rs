@@ -2,14 +2,19 @@ use ratatui::{
     layout::{Constraint, Direction, Layout, Alignment, Rect},
     style::{Color, Style, Modifier},
     text::{Span, Line, Text},
-    widgets::{Block, Borders, Paragraph, List, ListItem, ListState, Table, Row, Cell},
+    widgets::{Block, Borders, Paragraph, List, ListItem, Table, TableState, Row, Cell, Tabs},
     Frame,
 };
 
-use crate::app::{App, InputMode, InputField, TransactionType};
+use std::collections::BTreeMap;
+
+use crate::app::{
+    App, ContentTab, Focus, InputMode, InputField, TransactionType, TransactionStatus,
+    TransactionFocus, TRANSACTION_VISIBLE_COLUMNS,
+};
 
 /// Render the UI
-pub fn render(f: &mut Frame, app: &App) {
+pub fn render(f: &mut Frame, app: &mut App) {
     // Create main vertical layout
     let main_layout = Layout::default()
         .direction(Direction::Horizontal)
@@ -26,7 +31,15 @@ pub fn render(f: &mut Frame, app: &App) {
     render_content(f, app, main_layout[1]);
 }
 
-fn render_sidebar(f: &mut Frame, app: &App, area: Rect) {
+/// Shared highlight style for the selected row in the data tables
+fn selected_row_style() -> Style {
+    Style::default()
+        .bg(Color::DarkGray)
+        .fg(Color::Yellow)
+        .add_modifier(Modifier::BOLD)
+}
+
+fn render_sidebar(f: &mut Frame, app: &mut App, area: Rect) {
     // Create vertical layout for sidebar
     let sidebar_layout = Layout::default()
         .direction(Direction::Vertical)
@@ -44,25 +57,36 @@ fn render_sidebar(f: &mut Frame, app: &App, area: Rect) {
         .block(Block::default().borders(Borders::ALL));
     f.render_widget(title, sidebar_layout[0]);
     
-    // Create vertical navigation items
-    let nav_items: Vec<ListItem> = app.menu_state.items
+    // Build the collapsible bank → bucket → tag tree, one ListItem per visible
+    // node, indented and prefixed with a ▸/▾ glyph for collapsible parents.
+    let nav_items: Vec<ListItem> = app.sidebar_visible_nodes()
         .iter()
-        .map(|item| {
-            ListItem::new(Line::from(vec![
-                Span::styled(
-                    format!(" {} ", item),
-                    Style::default().fg(Color::White),
-                )
-            ]))
+        .map(|node| {
+            let glyph = if node.has_children {
+                if node.collapsed { "▸" } else { "▾" }
+            } else {
+                " "
+            };
+            let indent = "  ".repeat(node.indent);
+            ListItem::new(Line::from(vec![Span::styled(
+                format!("{}{} {}", indent, glyph, node.label),
+                Style::default().fg(Color::White),
+            )]))
         })
         .collect();
-    
-    // Create vertical navigation list
+
+    // Highlight the tree border when the sidebar holds focus.
+    let border_color = if app.focus == Focus::Sidebar {
+        Color::Cyan
+    } else {
+        Color::White
+    };
+
     let nav_list = List::new(nav_items)
         .block(Block::default()
-            .title("Menu")
+            .title("Accounts")
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::White)))
+            .border_style(Style::default().fg(border_color)))
         .style(Style::default().fg(Color::White))
         .highlight_style(
             Style::default()
@@ -71,17 +95,13 @@ fn render_sidebar(f: &mut Frame, app: &App, area: Rect) {
                 .add_modifier(Modifier::BOLD),
         )
         .highlight_symbol(" > ");  // Add indicator for selected item
-    
-    // Create a mutable list state and set the selected item
-    let mut list_state = ListState::default();
-    list_state.select(Some(app.menu_state.selected));
-    
-    // Render the list as a stateful widget
-    f.render_stateful_widget(nav_list, sidebar_layout[1], &mut list_state);
+
+    // Render the tree, keeping selection/scroll in the persistent sidebar state.
+    f.render_stateful_widget(nav_list, sidebar_layout[1], &mut app.sidebar_state);
     
     // Help text at bottom of sidebar
     let help_text = match app.input.mode {
-        InputMode::Normal => "n: New | ↑/↓: Navigate | Enter: Select | q: Quit",
+        InputMode::Normal => "n: New | ←/→: Pane | ↑/↓: Move | Enter: Toggle/Filter | Tab: View | q: Quit",
         InputMode::Editing { .. } => "↑/↓: Prev/Next Field | Enter: Next Field | Esc: Cancel",
     };
     
@@ -91,7 +111,7 @@ fn render_sidebar(f: &mut Frame, app: &App, area: Rect) {
     f.render_widget(help, sidebar_layout[2]);
 }
 
-fn render_content(f: &mut Frame, app: &App, area: Rect) {
+fn render_content(f: &mut Frame, app: &mut App, area: Rect) {
     let content_layout = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -100,22 +120,45 @@ fn render_content(f: &mut Frame, app: &App, area: Rect) {
         ].as_ref())
         .split(area);
     
+    // Split the content area into a sub-tab header row and the body beneath it.
+    let body_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),  // Sub-tab header
+            Constraint::Min(3),     // Body
+        ].as_ref())
+        .split(content_layout[0]);
+
+    // Enum-driven tab header so adding a variant adds a tab with no extra wiring.
+    let tab_titles: Vec<Line> = ContentTab::ALL
+        .iter()
+        .map(|t| Line::from(t.title()))
+        .collect();
+    let tabs = Tabs::new(tab_titles)
+        .select(app.content_tab.index())
+        .block(Block::default().borders(Borders::ALL))
+        .highlight_style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD));
+    f.render_widget(tabs, body_layout[0]);
+
     let content_block = Block::default()
         .borders(Borders::ALL)
         .title(app.menu_state.items[app.menu_state.selected].clone());
-    
-    f.render_widget(content_block.clone(), content_layout[0]);
-    
+
+    f.render_widget(content_block.clone(), body_layout[1]);
+
     // Calculate the inner area of the content block
-    let inner_area = content_block.inner(content_layout[0]);
-    
-    // Display different content based on selected tab
-    match app.menu_state.selected {
-        0 => render_banks(f, app, inner_area),
-        1 => render_buckets(f, app, inner_area),
-        2 => render_tags(f, app, inner_area),
-        3 => render_transactions(f, app, inner_area),
-        _ => {}
+    let inner_area = content_block.inner(body_layout[1]);
+
+    // Display different content based on the selected sub-tab, then category.
+    match app.content_tab {
+        ContentTab::List => match app.menu_state.selected {
+            0 => render_banks(f, app, inner_area),
+            1 => render_buckets(f, app, inner_area),
+            2 => render_tags(f, app, inner_area),
+            3 => render_transactions(f, app, inner_area),
+            _ => {}
+        },
+        ContentTab::Summary => render_summary(f, app, inner_area),
     }
     
     // Render message area
@@ -128,7 +171,111 @@ fn render_content(f: &mut Frame, app: &App, area: Rect) {
     }
 }
 
-fn render_banks(f: &mut Frame, app: &App, area: Rect) {
+/// Render the aggregate/analytics report for the selected category.
+fn render_summary(f: &mut Frame, app: &App, area: Rect) {
+    let heading = |text: &str| Line::from(Span::styled(
+        text.to_string(),
+        Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+    ));
+
+    let lines: Vec<Line> = match app.menu_state.selected {
+        1 => {
+            // Buckets: total allocated vs. each bank's balance.
+            let total_allocated: f64 = app.user_data.bucket.iter().map(|b| b.balance).sum();
+            let mut lines = vec![
+                heading("Bucket Summary"),
+                Line::from(format!("Total Allocated: {:.2}", total_allocated)),
+                Line::from(""),
+                heading("Per Bank"),
+            ];
+            for bank in &app.user_data.bank {
+                let allocated: f64 = app.user_data.bucket
+                    .iter()
+                    .filter(|b| b.bank.name == bank.name && b.bank.accountnumber == bank.accountnumber)
+                    .map(|b| b.balance)
+                    .sum();
+                lines.push(Line::from(format!(
+                    "  {} ({}): balance {:.2}, allocated {:.2}",
+                    bank.name, bank.accountnumber, bank.balance, allocated
+                )));
+            }
+            lines
+        }
+        3 => {
+            // Transactions: income/expense/net plus per-tag and per-bucket breakdowns.
+            let mut total_income = 0.0;
+            let mut total_expense = 0.0;
+            let mut by_tag: BTreeMap<String, f64> = BTreeMap::new();
+            let mut by_bucket: BTreeMap<String, f64> = BTreeMap::new();
+
+            for transaction in &app.user_data.transaction {
+                let signed = match transaction.transaction_type {
+                    TransactionType::Income => {
+                        total_income += transaction.amount;
+                        transaction.amount
+                    }
+                    TransactionType::Expense => {
+                        total_expense += transaction.amount;
+                        -transaction.amount
+                    }
+                };
+                for tag in &transaction.tags {
+                    *by_tag.entry(tag.name.clone()).or_insert(0.0) += signed;
+                    *by_bucket.entry(tag.bucket.name.clone()).or_insert(0.0) += signed;
+                }
+            }
+
+            let mut lines = vec![
+                heading("Transaction Summary"),
+                Line::from(format!("Total Income:  {:.2}", total_income)),
+                Line::from(format!("Total Expense: {:.2}", total_expense)),
+                Line::from(format!("Net Balance:   {:.2}", total_income - total_expense)),
+                Line::from(""),
+                heading("By Tag"),
+            ];
+            for (name, amount) in &by_tag {
+                lines.push(Line::from(format!("  {}: {:.2}", name, amount)));
+            }
+            lines.push(Line::from(""));
+            lines.push(heading("By Bucket"));
+            for (name, amount) in &by_bucket {
+                lines.push(Line::from(format!("  {}: {:.2}", name, amount)));
+            }
+            lines
+        }
+        0 => {
+            let total: f64 = app.user_data.bank.iter().map(|b| b.balance).sum();
+            vec![
+                heading("Bank Summary"),
+                Line::from(format!("Banks: {}", app.user_data.bank.len())),
+                Line::from(format!("Total Balance: {:.2}", total)),
+            ]
+        }
+        2 => {
+            let mut by_bucket: BTreeMap<String, usize> = BTreeMap::new();
+            for tag in &app.user_data.tag {
+                *by_bucket.entry(tag.bucket.name.clone()).or_insert(0) += 1;
+            }
+            let mut lines = vec![
+                heading("Tag Summary"),
+                Line::from(format!("Tags: {}", app.user_data.tag.len())),
+                Line::from(""),
+                heading("Per Bucket"),
+            ];
+            for (name, count) in &by_bucket {
+                lines.push(Line::from(format!("  {}: {}", name, count)));
+            }
+            lines
+        }
+        _ => vec![Line::from("No summary available.")],
+    };
+
+    let summary = Paragraph::new(Text::from(lines))
+        .alignment(Alignment::Left);
+    f.render_widget(summary, area);
+}
+
+fn render_banks(f: &mut Frame, app: &mut App, area: Rect) {
     match &app.input.mode {
         InputMode::Normal => {
             // In normal mode, display the list of banks
@@ -138,26 +285,47 @@ fn render_banks(f: &mut Frame, app: &App, area: Rect) {
                 f.render_widget(empty_msg, area);
             } else {
                 // Create a table to display banks
-                let header_cells = ["Name", "Account Number", "Balance"]
+                let header_cells = ["Name", "Account Number", "Balance", "Available", "Projected"]
                     .iter()
                     .map(|h| Cell::from(*h).style(Style::default().fg(Color::Yellow)));
                 let header = Row::new(header_cells)
                     .style(Style::default())
                     .height(1);
-                
+
                 let rows = app.user_data.bank.iter().map(|bank| {
+                    // The base balance already reflects every applied transaction
+                    // (added in `Pending`), so "Projected" is the base and
+                    // "Available" backs out the pending effects of every bucket
+                    // this bank backs, plus any amount held by open disputes, to
+                    // show only what has cleared and is free to spend.
+                    let pending: f64 = app.user_data.bucket
+                        .iter()
+                        .filter(|b| b.bank.name == bank.name && b.bank.accountnumber == bank.accountnumber)
+                        .map(|b| app.bucket_delta(&b.name, &TransactionStatus::Pending))
+                        .sum();
+                    let held: f64 = app.user_data.bucket
+                        .iter()
+                        .filter(|b| b.bank.name == bank.name && b.bank.accountnumber == bank.accountnumber)
+                        .map(|b| b.held)
+                        .sum();
+                    let projected = bank.balance;
+                    let available = bank.balance - pending - held;
                     let cells = [
                         Cell::from(bank.name.clone()),
                         Cell::from(bank.accountnumber.clone()),
                         Cell::from(format!("{:.2}", bank.balance)),
+                        Cell::from(format!("{:.2}", available)),
+                        Cell::from(format!("{:.2}", projected)),
                     ];
                     Row::new(cells)
                 });
-                
+
                 let widths = [
-                    Constraint::Percentage(30),
-                    Constraint::Percentage(40),
-                    Constraint::Percentage(30),
+                    Constraint::Percentage(25),
+                    Constraint::Percentage(25),
+                    Constraint::Percentage(16),
+                    Constraint::Percentage(17),
+                    Constraint::Percentage(17),
                 ];
                 
                 // Initialize table with required widths
@@ -166,9 +334,11 @@ fn render_banks(f: &mut Frame, app: &App, area: Rect) {
                     widths,
                 )
                 .header(header)
-                .block(Block::default());
-                
-                f.render_widget(table, area);
+                .block(Block::default())
+                .highlight_style(selected_row_style())
+                .highlight_symbol(" > ");
+
+                f.render_stateful_widget(table, area, &mut app.bank_state);
             }
         },
         InputMode::Editing { field, .. } => {
@@ -222,7 +392,7 @@ fn render_banks(f: &mut Frame, app: &App, area: Rect) {
     }
 }
 
-fn render_buckets(f: &mut Frame, app: &App, area: Rect) {
+fn render_buckets(f: &mut Frame, app: &mut App, area: Rect) {
     match &app.input.mode {
         InputMode::Normal => {
             // In normal mode, display the list of buckets
@@ -231,29 +401,44 @@ fn render_buckets(f: &mut Frame, app: &App, area: Rect) {
                     .alignment(Alignment::Center);
                 f.render_widget(empty_msg, area);
             } else {
-                // Create a table to display buckets
-                let header_cells = ["Name", "Balance", "Bank", "Account Number"]
+                // Create a table to display buckets. The base balance already
+                // reflects every applied transaction (added in `Pending`), so
+                // "Projected" is the base and "Available" backs out the pending
+                // effects and the amount held by open disputes, to show only
+                // what has cleared and is free to spend. "Held" surfaces the
+                // disputed amount directly.
+                let header_cells = ["Name", "Balance", "Held", "Available", "Projected", "Bank", "Account"]
                     .iter()
                     .map(|h| Cell::from(*h).style(Style::default().fg(Color::Yellow)));
                 let header = Row::new(header_cells)
                     .style(Style::default())
                     .height(1);
-                
+
                 let rows = app.user_data.bucket.iter().map(|bucket| {
+                    let projected = bucket.balance;
+                    let available = bucket.balance
+                        - app.bucket_delta(&bucket.name, &TransactionStatus::Pending)
+                        - bucket.held;
                     let cells = [
                         Cell::from(bucket.name.clone()),
                         Cell::from(format!("{:.2}", bucket.balance)),
+                        Cell::from(format!("{:.2}", bucket.held)),
+                        Cell::from(format!("{:.2}", available)),
+                        Cell::from(format!("{:.2}", projected)),
                         Cell::from(bucket.bank.name.clone()),
                         Cell::from(bucket.bank.accountnumber.clone()),
                     ];
                     Row::new(cells)
                 });
-                
+
                 let widths = [
-                    Constraint::Percentage(25),
-                    Constraint::Percentage(25),
-                    Constraint::Percentage(25),
-                    Constraint::Percentage(25),
+                    Constraint::Percentage(18),
+                    Constraint::Percentage(12),
+                    Constraint::Percentage(12),
+                    Constraint::Percentage(12),
+                    Constraint::Percentage(12),
+                    Constraint::Percentage(18),
+                    Constraint::Percentage(16),
                 ];
                 
                 // Initialize table with required widths
@@ -262,9 +447,11 @@ fn render_buckets(f: &mut Frame, app: &App, area: Rect) {
                     widths,
                 )
                 .header(header)
-                .block(Block::default());
-                
-                f.render_widget(table, area);
+                .block(Block::default())
+                .highlight_style(selected_row_style())
+                .highlight_symbol(" > ");
+
+                f.render_stateful_widget(table, area, &mut app.bucket_state);
             }
         },
         InputMode::Editing { field, .. } => {
@@ -330,7 +517,7 @@ fn render_buckets(f: &mut Frame, app: &App, area: Rect) {
     }
 }
 
-fn render_tags(f: &mut Frame, app: &App, area: Rect) {
+fn render_tags(f: &mut Frame, app: &mut App, area: Rect) {
     match &app.input.mode {
         InputMode::Normal => {
             // In normal mode, display the list of tags
@@ -368,9 +555,11 @@ fn render_tags(f: &mut Frame, app: &App, area: Rect) {
                     widths,
                 )
                 .header(header)
-                .block(Block::default());
-                
-                f.render_widget(table, area);
+                .block(Block::default())
+                .highlight_style(selected_row_style())
+                .highlight_symbol(" > ");
+
+                f.render_stateful_widget(table, area, &mut app.tag_state);
             }
         },
         InputMode::Editing { field, .. } => {
@@ -424,65 +613,137 @@ fn render_tags(f: &mut Frame, app: &App, area: Rect) {
     }
 }
 
-fn render_transactions(f: &mut Frame, app: &App, area: Rect) {
+/// Render one of the two stacked transaction lists with the frozen ID column
+/// and the scrolling column window shared across both halves.
+fn render_transaction_list(
+    f: &mut Frame,
+    area: Rect,
+    title: &str,
+    rows_data: &[[String; 5]],
+    column_offset: usize,
+    focused: bool,
+    state: &mut TableState,
+) {
+    let all_headers = ["ID", "Type", "Amount", "Description", "Tags"];
+    let all_widths = [10u16, 15, 15, 30, 30];
+
+    let scroll_count = TRANSACTION_VISIBLE_COLUMNS.saturating_sub(1);
+    let start = 1 + column_offset;
+    let end = (start + scroll_count).min(all_headers.len());
+    let visible: Vec<usize> = std::iter::once(0).chain(start..end).collect();
+
+    let header_cells = visible
+        .iter()
+        .map(|&i| Cell::from(all_headers[i]).style(Style::default().fg(Color::Yellow)));
+    let header = Row::new(header_cells)
+        .style(Style::default())
+        .height(1);
+
+    let rows = rows_data.iter().map(|cells| {
+        Row::new(visible.iter().map(|&i| Cell::from(cells[i].clone())).collect::<Vec<_>>())
+    });
+
+    let widths: Vec<Constraint> = visible
+        .iter()
+        .map(|&i| Constraint::Percentage(all_widths[i]))
+        .collect();
+
+    // Append ◀/▶ to the title when columns are hidden off either edge.
+    let mut title = title.to_string();
+    if column_offset > 0 {
+        title.push_str(" ◀");
+    }
+    if end < all_headers.len() {
+        title.push_str(" ▶");
+    }
+
+    let border_style = if focused {
+        Style::default().fg(Color::Cyan)
+    } else {
+        Style::default()
+    };
+
+    let table = Table::new(
+        rows,
+        widths,
+    )
+    .header(header)
+    .block(Block::default().borders(Borders::ALL).title(title).border_style(border_style))
+    .highlight_style(selected_row_style())
+    .highlight_symbol(" > ");
+
+    f.render_stateful_widget(table, area, state);
+}
+
+fn render_transactions(f: &mut Frame, app: &mut App, area: Rect) {
     match &app.input.mode {
         InputMode::Normal => {
-            // In normal mode, display the list of transactions
-            if app.user_data.transaction.is_empty() {
-                let empty_msg = Paragraph::new("No transactions available. Press 'n' to add a new transaction.")
-                    .alignment(Alignment::Center);
-                f.render_widget(empty_msg, area);
-            } else {
-                // Create a table to display transactions
-                let header_cells = ["ID", "Type", "Amount", "Description", "Tags"]
+            // Split the area into stacked Pending / Completed lists.
+            let lists = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Percentage(50),
+                    Constraint::Percentage(50),
+                ].as_ref())
+                .split(area);
+
+            let cells_for = |status: &TransactionStatus| -> Vec<[String; 5]> {
+                app.user_data.transaction
                     .iter()
-                    .map(|h| Cell::from(*h).style(Style::default().fg(Color::Yellow)));
-                let header = Row::new(header_cells)
-                    .style(Style::default())
-                    .height(1);
-                
-                let rows = app.user_data.transaction.iter().map(|transaction| {
-                    let txn_type = match transaction.transaction_type {
-                        TransactionType::Income => "Income",
-                        TransactionType::Expense => "Expense",
-                    };
-                    
-                    let tags = transaction.tags
-                        .iter()
-                        .map(|tag| tag.name.clone())
-                        .collect::<Vec<String>>()
-                        .join(", ");
-                    
-                    let cells = [
-                        Cell::from(transaction.id.to_string()),
-                        Cell::from(txn_type),
-                        Cell::from(format!("{:.2}", transaction.amount)),
-                        Cell::from(transaction.description.clone()),
-                        Cell::from(tags),
-                    ];
-                    Row::new(cells)
-                });
-                
-                let widths = [
-                    Constraint::Percentage(10),
-                    Constraint::Percentage(15),
-                    Constraint::Percentage(15),
-                    Constraint::Percentage(30),
-                    Constraint::Percentage(30),
-                ];
-                
-                // Initialize table with required widths
-                let table = Table::new(
-                    rows,
-                    widths,
-                )
-                .header(header)
-                .block(Block::default());
-                
-                f.render_widget(table, area);
-            }
+                    .filter(|t| &t.status == status && app.transaction_matches_filter(t))
+                    .map(|t| {
+                        let txn_type = match t.transaction_type {
+                            TransactionType::Income => "Income",
+                            TransactionType::Expense => "Expense",
+                        };
+                        let tags = t.tags
+                            .iter()
+                            .map(|tag| tag.name.clone())
+                            .collect::<Vec<String>>()
+                            .join(", ");
+                        [
+                            t.id.to_string(),
+                            txn_type.to_string(),
+                            format!("{:.2}", t.amount),
+                            t.description.clone(),
+                            tags,
+                        ]
+                    })
+                    .collect()
+            };
+
+            let pending_rows = cells_for(&TransactionStatus::Pending);
+            let cleared_rows = cells_for(&TransactionStatus::Cleared);
+            let offset = app.column_offset;
+            let pending_focused = app.transaction_focus == TransactionFocus::Pending;
+
+            render_transaction_list(
+                f, lists[0], "(P)ending", &pending_rows, offset, pending_focused, &mut app.pending_state,
+            );
+            render_transaction_list(
+                f, lists[1], "Completed", &cleared_rows, offset, !pending_focused, &mut app.cleared_state,
+            );
         },
         InputMode::Editing { field, .. } => {
+            // Dispute-lifecycle actions collect a single transaction id.
+            if let Some(title) = match field {
+                InputField::DisputeTxId => Some("Dispute — Transaction ID"),
+                InputField::ResolveTxId => Some("Resolve — Transaction ID"),
+                InputField::ChargebackTxId => Some("Chargeback — Transaction ID"),
+                _ => None,
+            } {
+                let layout = Layout::default()
+                    .direction(Direction::Vertical)
+                    .margin(1)
+                    .constraints([Constraint::Length(3)].as_ref())
+                    .split(area);
+                let id_input = Paragraph::new(app.input.transaction_action_id.clone())
+                    .style(Style::default().fg(Color::Yellow))
+                    .block(Block::default().borders(Borders::ALL).title(title));
+                f.render_widget(id_input, layout[0]);
+                return;
+            }
+
             // In editing mode, show the input form for a transaction
             let input_layout = Layout::default()
                 .direction(Direction::Vertical)